@@ -0,0 +1,55 @@
+//! Split a large input parquet file into row-group-aligned scan ranges.
+//!
+//! A compaction scan normally treats one file as one indivisible unit, so a partition dominated
+//! by a few oversized files is read serially. Splitting each large file into several scan ranges
+//! — each covering a whole number of row groups — turns it into independent scan units the
+//! executor can read concurrently. Row groups are never split across ranges, so each range is a
+//! self-contained, decodable chunk.
+
+use std::ops::Range;
+
+use parquet::file::metadata::RowGroupMetaData;
+
+/// Compute the scan ranges, as half-open row-group index ranges, for a file whose row groups are
+/// described by `row_groups`.
+///
+/// Files whose total size is below `min_file_bytes_to_split` (or `min_file_bytes_to_split == 0`)
+/// yield a single range covering the whole file. Otherwise row groups are accumulated into a
+/// range until their combined compressed size reaches the threshold, then a new range is started,
+/// so every range but possibly the last is at least `min_file_bytes_to_split` bytes and all are
+/// row-group-aligned.
+pub fn split_scan_ranges(
+    row_groups: &[RowGroupMetaData],
+    min_file_bytes_to_split: u64,
+) -> Vec<Range<usize>> {
+    let sizes: Vec<i64> = row_groups.iter().map(|rg| rg.compressed_size()).collect();
+    let total: i64 = sizes.iter().sum();
+
+    let splittable = min_file_bytes_to_split > 0
+        && row_groups.len() > 1
+        && total >= min_file_bytes_to_split as i64;
+    if !splittable {
+        return vec![0..row_groups.len()];
+    }
+
+    let threshold = min_file_bytes_to_split as i64;
+    let mut ranges = Vec::new();
+    let mut range_start_rg = 0;
+    let mut acc = 0;
+    for (i, size) in sizes.iter().enumerate() {
+        acc += size;
+        // Cut after a whole row group once we have accumulated at least the threshold, unless
+        // this is the final row group (handled after the loop).
+        if acc >= threshold && i + 1 < sizes.len() {
+            ranges.push(range_start_rg..i + 1);
+            range_start_rg = i + 1;
+            acc = 0;
+        }
+    }
+    // Trailing row groups that did not reach the threshold on their own.
+    if range_start_rg < sizes.len() {
+        ranges.push(range_start_rg..sizes.len());
+    }
+
+    ranges
+}
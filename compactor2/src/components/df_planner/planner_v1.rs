@@ -0,0 +1,131 @@
+use std::{fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
+use iox_query::{
+    exec::{Executor, ExecutorType},
+    frontend::reorg::ReorgPlanner,
+    QueryChunk,
+};
+use parquet_file::storage::ParquetStorage;
+
+use crate::{partition_info::PartitionInfo, plan_ir::PlanIR};
+
+use super::{
+    file_split::split_scan_ranges, round_robin::round_robin_repartition, DataFusionPlanner,
+};
+
+/// Builds a DataFusion [`ExecutionPlan`] for a compaction branch.
+#[derive(Debug)]
+pub struct V1DataFusionPlanner {
+    store: ParquetStorage,
+    exec: Arc<Executor>,
+    /// Insert round-robin repartitioning above the parquet scans.
+    enable_round_robin_repartition: bool,
+    /// Number of partitions the round-robin repartitioning fans out to.
+    target_partitions: usize,
+    /// Minimum file size, in bytes, at which an input file is split into row-group-aligned scan
+    /// ranges. `0` disables splitting.
+    min_file_bytes_to_split: u64,
+}
+
+impl V1DataFusionPlanner {
+    /// Create a new planner.
+    pub fn new(
+        store: ParquetStorage,
+        exec: Arc<Executor>,
+        enable_round_robin_repartition: bool,
+        target_partitions: usize,
+        min_file_bytes_to_split: u64,
+    ) -> Self {
+        Self {
+            store,
+            exec,
+            enable_round_robin_repartition,
+            target_partitions,
+            min_file_bytes_to_split,
+        }
+    }
+
+    /// Optionally fan the scans out across cores via round-robin repartitioning.
+    ///
+    /// Repartitioning is suppressed throughout the subtree beneath any order-sensitive operator
+    /// (sort, sort-preserving merge, dedup, split) so the output ordering is preserved; see
+    /// [`round_robin_repartition`].
+    fn maybe_repartition(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>, DataFusionError> {
+        if self.enable_round_robin_repartition {
+            round_robin_repartition(plan, self.target_partitions)
+        } else {
+            Ok(plan)
+        }
+    }
+}
+
+impl Display for V1DataFusionPlanner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "v1")
+    }
+}
+
+#[async_trait]
+impl DataFusionPlanner for V1DataFusionPlanner {
+    async fn plan(
+        &self,
+        ir: &PlanIR,
+        partition: Arc<PartitionInfo>,
+    ) -> Result<Arc<dyn ExecutionPlan>, DataFusionError> {
+        let ctx = self.exec.new_context(ExecutorType::Reorg);
+
+        // Turn each input file into one or more queryable chunks backed by the object store.
+        // When splitting is enabled, large files are divided into row-group-aligned scan ranges
+        // so they become independent scan units the executor can read concurrently. When it is
+        // disabled (the default) we build one chunk per file and, crucially, avoid the per-file
+        // row-group metadata round trip.
+        let mut chunks: Vec<Arc<dyn QueryChunk>> = Vec::with_capacity(ir.files().len());
+        for file in ir.files() {
+            if self.min_file_bytes_to_split == 0 {
+                chunks.push(partition.to_query_chunk(file, self.store.clone()));
+                continue;
+            }
+            let row_groups = self.store.row_group_metadata(file).await?;
+            for range in split_scan_ranges(&row_groups, self.min_file_bytes_to_split) {
+                chunks.push(partition.to_query_chunk_for_range(
+                    file,
+                    self.store.clone(),
+                    range,
+                ));
+            }
+        }
+
+        let sort_key = partition
+            .sort_key
+            .as_ref()
+            .expect("no partition sort key")
+            .filter_to(&partition.column_ids(), partition.partition_id.get());
+
+        // Build the logical plan for this branch from the IR.
+        let logical_plan = match ir {
+            PlanIR::Compact { .. } => ReorgPlanner::new().compact_plan(
+                Arc::from(partition.table.name.as_str()),
+                &partition.schema,
+                chunks,
+                sort_key,
+            )?,
+            PlanIR::Split { split_times, .. } => ReorgPlanner::new().split_plan(
+                Arc::from(partition.table.name.as_str()),
+                &partition.schema,
+                chunks,
+                sort_key,
+                split_times.clone(),
+            )?,
+        };
+
+        // Lower to a physical plan, then optionally fan the scans out across cores, leaving every
+        // subtree beneath an order-sensitive operator (sort, merge, dedup, split) intact.
+        let physical_plan = ctx.create_physical_plan(&logical_plan).await?;
+        self.maybe_repartition(physical_plan)
+    }
+}
@@ -0,0 +1,82 @@
+//! Round-robin repartitioning of a compaction physical plan.
+//!
+//! A single compaction often under-utilizes CPU because the parquet scan feeds a narrow
+//! pipeline. Round-robin [`RepartitionExec`] nodes are inserted above the leaf scans that have no
+//! order-sensitive operator above them, so the operators over those scans fan out across
+//! `target_partitions` partitions. Sort, sort-preserving merge, dedup and split work is not fanned
+//! out: no scan beneath one of those operators is repartitioned.
+//!
+//! Repartitioning is suppressed throughout the subtree beneath any order-sensitive operator
+//! (the final sort, the dedup it feeds, and the split that depends on it): reshuffling batches
+//! anywhere below such an operator would destroy the single, ordered input partition it relies
+//! on. Because this rewrite does not run `EnforceDistribution`, the suppression is our only
+//! guard, so it must be conservative and transitive.
+
+use std::sync::Arc;
+
+use datafusion::{
+    error::Result,
+    physical_plan::{
+        repartition::RepartitionExec, sorts::sort::SortExec,
+        sorts::sort_preserving_merge::SortPreservingMergeExec, ExecutionPlan, Partitioning,
+    },
+};
+use iox_query::{exec::split::StreamSplitExec, provider::DeduplicateExec};
+
+/// Rewrite `plan` so that parquet scans feeding non-order-sensitive operators are fanned out to
+/// `target_partitions` via round-robin repartitioning.
+///
+/// Returns the plan unchanged when `target_partitions < 2` (fan-out would be a no-op).
+pub fn round_robin_repartition(
+    plan: Arc<dyn ExecutionPlan>,
+    target_partitions: usize,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    if target_partitions < 2 {
+        return Ok(plan);
+    }
+    rewrite(plan, target_partitions, false)
+}
+
+/// An operator that requires a single, ordered input partition, so nothing beneath it may be
+/// repartitioned.
+fn is_order_sensitive(plan: &Arc<dyn ExecutionPlan>) -> bool {
+    let any = plan.as_any();
+    any.is::<SortExec>()
+        || any.is::<SortPreservingMergeExec>()
+        || any.is::<DeduplicateExec>()
+        || any.is::<StreamSplitExec>()
+}
+
+/// Recursively rewrite `plan`.
+///
+/// `under_order_sensitive` is `true` once we are anywhere below an order-sensitive operator; it
+/// is propagated to the whole subtree so suppression is transitive, not depth-1.
+fn rewrite(
+    plan: Arc<dyn ExecutionPlan>,
+    target_partitions: usize,
+    under_order_sensitive: bool,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    // Once we cross an order-sensitive operator the entire subtree below it must keep its
+    // existing partitioning.
+    let children_under_order_sensitive = under_order_sensitive || is_order_sensitive(&plan);
+
+    let new_children = plan
+        .children()
+        .into_iter()
+        .map(|child| rewrite(child, target_partitions, children_under_order_sensitive))
+        .collect::<Result<Vec<_>>>()?;
+    let plan = plan.with_new_children(new_children)?;
+
+    // Only wrap leaf scans, and only when doing so would actually increase parallelism and we are
+    // not anywhere beneath an order-sensitive operator.
+    let is_leaf = plan.children().is_empty();
+    let current_partitions = plan.output_partitioning().partition_count();
+    if !under_order_sensitive && is_leaf && current_partitions < target_partitions {
+        return Ok(Arc::new(RepartitionExec::try_new(
+            plan,
+            Partitioning::RoundRobinBatch(target_partitions),
+        )?));
+    }
+
+    Ok(plan)
+}
@@ -0,0 +1,203 @@
+use std::fmt::{Debug, Display};
+
+use data_types::ParquetFile;
+
+use crate::{
+    components::{files_split::FilesSplit, target_level_chooser::TargetLevelChooser},
+    file_classification::FileClassification,
+    partition_info::PartitionInfo,
+};
+
+use super::FileClassifier;
+
+/// Classifier that splits a partition's files, level by level, into the files that must be
+/// compacted, the files that can be moved up with a catalog-only commit, and the files left
+/// untouched this round.
+///
+/// The trivially-movable files identified by [`trivial_move_split`] are routed to
+/// [`FileClassification::files_to_upgrade`], so they skip the DataFusion exec and the
+/// object-store read/write entirely — they are promoted to `target_level` by flipping their
+/// `compaction_level` alone.
+///
+/// [`trivial_move_split`]: crate::components::files_split::trivial_move_split
+pub struct SplitBasedFileClassifier<TC, TS, NO, TU, TM>
+where
+    TC: TargetLevelChooser,
+    TS: FilesSplit,
+    NO: FilesSplit,
+    TU: FilesSplit,
+    TM: FilesSplit,
+{
+    target_level_chooser: TC,
+    target_level_split: TS,
+    non_overlap_split: NO,
+    upgrade_split: TU,
+    trivial_move_split: TM,
+}
+
+impl<TC, TS, NO, TU, TM> SplitBasedFileClassifier<TC, TS, NO, TU, TM>
+where
+    TC: TargetLevelChooser,
+    TS: FilesSplit,
+    NO: FilesSplit,
+    TU: FilesSplit,
+    TM: FilesSplit,
+{
+    /// Create a new [`SplitBasedFileClassifier`].
+    pub fn new(
+        target_level_chooser: TC,
+        target_level_split: TS,
+        non_overlap_split: NO,
+        upgrade_split: TU,
+        trivial_move_split: TM,
+    ) -> Self {
+        Self {
+            target_level_chooser,
+            target_level_split,
+            non_overlap_split,
+            upgrade_split,
+            trivial_move_split,
+        }
+    }
+}
+
+impl<TC, TS, NO, TU, TM> Debug for SplitBasedFileClassifier<TC, TS, NO, TU, TM>
+where
+    TC: TargetLevelChooser,
+    TS: FilesSplit,
+    NO: FilesSplit,
+    TU: FilesSplit,
+    TM: FilesSplit,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SplitBasedFileClassifier").finish()
+    }
+}
+
+impl<TC, TS, NO, TU, TM> Display for SplitBasedFileClassifier<TC, TS, NO, TU, TM>
+where
+    TC: TargetLevelChooser,
+    TS: FilesSplit,
+    NO: FilesSplit,
+    TU: FilesSplit,
+    TM: FilesSplit,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "split_based")
+    }
+}
+
+impl<TC, TS, NO, TU, TM> FileClassifier for SplitBasedFileClassifier<TC, TS, NO, TU, TM>
+where
+    TC: TargetLevelChooser,
+    TS: FilesSplit,
+    NO: FilesSplit,
+    TU: FilesSplit,
+    TM: FilesSplit,
+{
+    fn classify(&self, _partition_info: &PartitionInfo, files: Vec<ParquetFile>) -> FileClassification {
+        let target_level = self.target_level_chooser.detect(&files);
+
+        // Files above the target level are kept aside; the rest (files at or below the target
+        // level) are the candidates for this round.
+        let (candidates, mut files_to_keep) = self.target_level_split.apply(files, target_level);
+
+        // Trivially-movable files (non-overlapping, below target size) are promoted with a
+        // catalog-only commit, so take them out before anything else touches a DataFusion plan.
+        let (movable, candidates) = self.trivial_move_split.apply(candidates, target_level);
+
+        // The remaining upgrade split catches any other file that can skip compaction.
+        let (mut files_to_upgrade, candidates) = self.upgrade_split.apply(candidates, target_level);
+
+        // Whatever overlaps must be rewritten; the non-overlapping remainder is kept for a later
+        // round.
+        let (files_to_compact, non_overlap_keep) =
+            self.non_overlap_split.apply(candidates, target_level);
+
+        files_to_upgrade.extend(movable);
+        files_to_keep.extend(non_overlap_keep);
+
+        FileClassification {
+            target_level,
+            files_to_compact,
+            files_to_upgrade,
+            files_to_keep,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::{CompactionLevel, ParquetFileId};
+    use iox_tests::ParquetFileBuilder;
+
+    use crate::components::{
+        files_split::{
+            target_level_non_overlap_split::TargetLevelNonOverlapSplit,
+            target_level_target_level_split::TargetLevelTargetLevelSplit,
+            target_level_upgrade_split::TargetLevelUpgradeSplit,
+            trivial_move_split::TrivialMoveSplit,
+        },
+        level_exist::one_level::OneLevelExist,
+        target_level_chooser::target_level::TargetLevelTargetLevelChooser,
+    };
+
+    use super::*;
+
+    const MAX_SIZE: u64 = 100 * 1024 * 1024;
+
+    fn file(id: i64, level: CompactionLevel, min: i64, max: i64, size: i64) -> ParquetFile {
+        ParquetFileBuilder::new(id)
+            .with_compaction_level(level)
+            .with_time_range(min, max)
+            .with_file_size_bytes(size)
+            .build()
+    }
+
+    fn classifier() -> impl FileClassifier {
+        SplitBasedFileClassifier::new(
+            TargetLevelTargetLevelChooser::new(OneLevelExist::new()),
+            TargetLevelTargetLevelSplit::new(),
+            TargetLevelNonOverlapSplit::new(),
+            TargetLevelUpgradeSplit::new(MAX_SIZE),
+            TrivialMoveSplit::new(MAX_SIZE),
+        )
+    }
+
+    #[test]
+    fn trivially_movable_files_are_upgraded_not_compacted() {
+        // Two small, non-overlapping L0 files with no L1 present: both can be promoted to L1 by a
+        // level-only commit, so nothing should be handed to a DataFusion plan.
+        let files = vec![
+            file(1, CompactionLevel::Initial, 0, 100, 10),
+            file(2, CompactionLevel::Initial, 200, 300, 10),
+        ];
+
+        let partition = PartitionInfo::arbitrary();
+        let classification = classifier().classify(&partition, files);
+
+        assert_eq!(classification.target_level, CompactionLevel::FileNonOverlapped);
+        assert!(
+            classification.files_to_compact.is_empty(),
+            "no DataFusion plan should be produced for trivially-movable files"
+        );
+        let mut upgraded: Vec<_> = classification.files_to_upgrade.iter().map(|f| f.id).collect();
+        upgraded.sort();
+        assert_eq!(upgraded, vec![ParquetFileId::new(1), ParquetFileId::new(2)]);
+    }
+
+    #[test]
+    fn overlapping_files_are_compacted() {
+        // Two overlapping L0 files cannot be trivially moved and must be rewritten.
+        let files = vec![
+            file(1, CompactionLevel::Initial, 0, 200, 10),
+            file(2, CompactionLevel::Initial, 100, 300, 10),
+        ];
+
+        let partition = PartitionInfo::arbitrary();
+        let classification = classifier().classify(&partition, files);
+
+        assert_eq!(classification.files_to_compact.len(), 2);
+        assert!(classification.files_to_upgrade.is_empty());
+    }
+}
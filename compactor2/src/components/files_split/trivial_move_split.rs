@@ -0,0 +1,97 @@
+use std::fmt::Display;
+
+use data_types::{CompactionLevel, ParquetFile, Timestamp};
+
+use super::FilesSplit;
+
+/// Splits off the files that can be moved to the target level without rewriting them through
+/// DataFusion.
+///
+/// A file is *trivially movable* when it is already at the level directly below `target_level`,
+/// its size is below `max_desired_file_size_bytes`, and its `[min_time, max_time]` range is
+/// disjoint from every file already at `target_level` as well as from every other movable file.
+/// Such a file can be promoted with a catalog-only [`Commit`] that flips its
+/// `compaction_level` (L0→L1, L1→L2), skipping all object-store reads and writes.
+///
+/// [`Commit`]: crate::components::commit::Commit
+#[derive(Debug)]
+pub struct TrivialMoveSplit {
+    max_desired_file_size_bytes: u64,
+}
+
+impl TrivialMoveSplit {
+    /// Create a new [`TrivialMoveSplit`].
+    pub fn new(max_desired_file_size_bytes: u64) -> Self {
+        Self {
+            max_desired_file_size_bytes,
+        }
+    }
+}
+
+impl Display for TrivialMoveSplit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "trivial_move")
+    }
+}
+
+impl FilesSplit for TrivialMoveSplit {
+    /// Returns `(movable, rest)` where `movable` can be promoted to `target_level` by a
+    /// level-only commit and `rest` must go through real compaction.
+    fn apply(
+        &self,
+        files: Vec<ParquetFile>,
+        target_level: CompactionLevel,
+    ) -> (Vec<ParquetFile>, Vec<ParquetFile>) {
+        // Files already sitting at the destination level constrain which candidates can move:
+        // a candidate may only move into a time range not already occupied there.
+        let mut occupied: Vec<(Timestamp, Timestamp)> = files
+            .iter()
+            .filter(|f| f.compaction_level == target_level)
+            .map(|f| (f.min_time, f.max_time))
+            .collect();
+
+        // Only files one level below the destination are eligible to be moved up.
+        let source_level = match target_level {
+            CompactionLevel::FileNonOverlapped => CompactionLevel::Initial,
+            CompactionLevel::Final => CompactionLevel::FileNonOverlapped,
+            // Initial is the lowest level; nothing can move into it.
+            CompactionLevel::Initial => return (vec![], files),
+        };
+
+        // Consider candidates in time order so the disjointness check is stable and independent
+        // of catalog ordering.
+        let mut candidates: Vec<ParquetFile> = files
+            .iter()
+            .filter(|f| {
+                f.compaction_level == source_level
+                    && f.file_size_bytes < self.max_desired_file_size_bytes as i64
+            })
+            .cloned()
+            .collect();
+        candidates.sort_by_key(|f| (f.min_time, f.max_time));
+
+        let mut movable = Vec::with_capacity(candidates.len());
+        let mut movable_ids: Vec<_> = Vec::with_capacity(candidates.len());
+        for file in candidates {
+            let range = (file.min_time, file.max_time);
+            if occupied.iter().all(|other| disjoint(range, *other)) {
+                occupied.push(range);
+                movable_ids.push(file.id);
+                movable.push(file);
+            }
+        }
+
+        let rest = files
+            .into_iter()
+            .filter(|f| !movable_ids.contains(&f.id))
+            .collect();
+
+        (movable, rest)
+    }
+}
+
+/// Two inclusive `[min, max]` time ranges are disjoint when one ends strictly before the other
+/// begins.
+fn disjoint(a: (Timestamp, Timestamp), b: (Timestamp, Timestamp)) -> bool {
+    a.1 < b.0 || b.1 < a.0
+}
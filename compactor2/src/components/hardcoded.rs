@@ -1,6 +1,9 @@
-//! Current hardcoded component setup.
+//! Default component setup.
 //!
-//! TODO: Make this a runtime-config.
+//! Which implementation of each component trait is instantiated (and in what wrapper
+//! order) is driven by the declarative [`ComponentsConfig`] namespace on [`Config`].
+//! [`hardcoded_components`] is a thin default that reads that config and assembles the
+//! [`Components`] accordingly.
 
 use std::{sync::Arc, time::Duration};
 
@@ -12,7 +15,11 @@ use crate::{
         namespaces_source::catalog::CatalogNamespacesSource,
         tables_source::catalog::CatalogTablesSource,
     },
-    config::{AlgoVersion, Config, PartitionsSourceConfig},
+    config::{
+        AlgoVersion, CommitSink, Config, DataFusionPlanExecKind, FileClassifierKind,
+        ComponentsConfigError, PartitionDoneSinkKind, PartitionFilterSet, PartitionsSourceConfig,
+        ScratchpadKind,
+    },
     error::ErrorKind,
     object_store::ignore_writes::IgnoreWrites,
 };
@@ -38,6 +45,7 @@ use super::{
         target_level_non_overlap_split::TargetLevelNonOverlapSplit,
         target_level_target_level_split::TargetLevelTargetLevelSplit,
         target_level_upgrade_split::TargetLevelUpgradeSplit,
+        trivial_move_split::TrivialMoveSplit,
     },
     id_only_partition_filter::{
         and::AndIdOnlyPartitionFilter, shard::ShardPartitionFilter, IdOnlyPartitionFilter,
@@ -84,16 +92,33 @@ use super::{
     round_split::all_now::AllNowRoundSplit,
     scratchpad::{noop::NoopScratchpadGen, prod::ProdScratchpadGen, ScratchpadGen},
     skipped_compactions_source::catalog::CatalogSkippedCompactionsSource,
-    target_level_chooser::target_level::TargetLevelTargetLevelChooser,
+    target_level_chooser::{
+        bottommost::BottommostTargetLevelChooser, target_level::TargetLevelTargetLevelChooser,
+    },
     Components,
 };
 
-/// Get hardcoded components.
+/// Validate the config and assemble the [`Components`] it selects.
+///
+/// This is the entry point used at startup: it runs [`Config::validate`] first so an invalid
+/// selection (e.g. a catalog sink together with shadow mode) is reported as an error before any
+/// component is built, rather than panicking or silently misbehaving later.
+pub fn build_components(config: &Config) -> Result<Arc<Components>, ComponentsConfigError> {
+    config.validate()?;
+    Ok(hardcoded_components(config))
+}
+
+/// Assemble the [`Components`] selected by the [`ComponentsConfig`](crate::config::ComponentsConfig)
+/// namespace on `config`.
+///
+/// The config is expected to have been validated via [`Config::validate`] (see
+/// [`build_components`]), so by the time we get here every selection is known to be internally
+/// consistent.
 pub fn hardcoded_components(config: &Config) -> Arc<Components> {
     // TODO: partitions source: Implementing ID-based sharding / hash-partitioning so we can run multiple compactors in
     //       parallel. This should be a wrapper around the existing partions source.
 
-    let partitions_source: Arc<dyn PartitionsSource> = match &config.partitions_source {
+    let partitions_source: Arc<dyn PartitionsSource> = match &config.components.partitions_source {
         PartitionsSourceConfig::CatalogRecentWrites => {
             Arc::new(CatalogToCompactPartitionsSource::new(
                 config.backoff_config.clone(),
@@ -124,20 +149,24 @@ pub fn hardcoded_components(config: &Config) -> Arc<Components> {
         partitions_source,
     );
 
+    // A partition always has to have files; every other gate is part of the "standard" stack and
+    // can be dropped (e.g. for A/B testing or forced backfills) via the components config.
     let mut partition_filters: Vec<Arc<dyn PartitionFilter>> = vec![];
     partition_filters.push(Arc::new(HasFilesPartitionFilter::new()));
-    if !config.ignore_partition_skip_marker {
-        partition_filters.push(Arc::new(NeverSkippedPartitionFilter::new(
-            CatalogSkippedCompactionsSource::new(
-                config.backoff_config.clone(),
-                Arc::clone(&config.catalog),
-            ),
+    if config.components.partition_filters == PartitionFilterSet::Standard {
+        if !config.ignore_partition_skip_marker {
+            partition_filters.push(Arc::new(NeverSkippedPartitionFilter::new(
+                CatalogSkippedCompactionsSource::new(
+                    config.backoff_config.clone(),
+                    Arc::clone(&config.catalog),
+                ),
+            )));
+        }
+        partition_filters.push(Arc::new(MaxNumColumnsPartitionFilter::new(
+            config.max_num_columns_per_table,
         )));
+        partition_filters.append(&mut version_specific_partition_filters(config));
     }
-    partition_filters.push(Arc::new(MaxNumColumnsPartitionFilter::new(
-        config.max_num_columns_per_table,
-    )));
-    partition_filters.append(&mut version_specific_partition_filters(config));
 
     let partition_resource_limit_filters: Vec<Arc<dyn PartitionFilter>> = vec![
         Arc::new(MaxFilesPartitionFilter::new(
@@ -148,22 +177,21 @@ pub fn hardcoded_components(config: &Config) -> Arc<Components> {
         )),
     ];
 
-    let partition_done_sink: Arc<dyn PartitionDoneSink> = if config.shadow_mode {
-        Arc::new(MockPartitionDoneSink::new())
-    } else {
-        Arc::new(CatalogPartitionDoneSink::new(
+    let partition_done_sink: Arc<dyn PartitionDoneSink> = match config.components.partition_done_sink
+    {
+        PartitionDoneSinkKind::Mock => Arc::new(MockPartitionDoneSink::new()),
+        PartitionDoneSinkKind::Catalog => Arc::new(CatalogPartitionDoneSink::new(
             config.backoff_config.clone(),
             Arc::clone(&config.catalog),
-        ))
+        )),
     };
 
-    let commit: Arc<dyn Commit> = if config.shadow_mode {
-        Arc::new(MockCommit::new())
-    } else {
-        Arc::new(CatalogCommit::new(
+    let commit: Arc<dyn Commit> = match config.components.commit {
+        CommitSink::Mock => Arc::new(MockCommit::new()),
+        CommitSink::Catalog => Arc::new(CatalogCommit::new(
             config.backoff_config.clone(),
             Arc::clone(&config.catalog),
-        ))
+        )),
     };
 
     let scratchpad_store_output = if config.shadow_mode {
@@ -227,21 +255,21 @@ pub fn hardcoded_components(config: &Config) -> Arc<Components> {
     let partition_continue_conditions = "continue_conditions";
     let partition_resource_limit_conditions = "resource_limit_conditions";
 
-    let scratchpad_gen: Arc<dyn ScratchpadGen> = if config.simulate_without_object_store {
-        Arc::new(NoopScratchpadGen::new())
-    } else {
-        Arc::new(ProdScratchpadGen::new(
+    let scratchpad_gen: Arc<dyn ScratchpadGen> = match config.components.scratchpad {
+        ScratchpadKind::Noop => Arc::new(NoopScratchpadGen::new()),
+        ScratchpadKind::Prod => Arc::new(ProdScratchpadGen::new(
             config.partition_scratchpad_concurrency,
             config.backoff_config.clone(),
             Arc::clone(config.parquet_store_real.object_store()),
             Arc::clone(config.parquet_store_scratchpad.object_store()),
             scratchpad_store_output,
-        ))
+        )),
     };
-    let df_plan_exec: Arc<dyn DataFusionPlanExec> = if config.simulate_without_object_store {
-        Arc::new(NoopDataFusionPlanExec::new())
-    } else {
-        Arc::new(DedicatedDataFusionPlanExec::new(Arc::clone(&config.exec)))
+    let df_plan_exec: Arc<dyn DataFusionPlanExec> = match config.components.df_plan_exec {
+        DataFusionPlanExecKind::Noop => Arc::new(NoopDataFusionPlanExec::new()),
+        DataFusionPlanExecKind::Dedicated => {
+            Arc::new(DedicatedDataFusionPlanExec::new(Arc::clone(&config.exec)))
+        }
     };
     let parquet_files_sink: Arc<dyn ParquetFilesSink> =
         if let Some(sink) = config.parquet_files_sink_override.as_ref() {
@@ -253,6 +281,7 @@ pub fn hardcoded_components(config: &Config) -> Arc<Components> {
                         config.shard_id,
                         config.parquet_store_scratchpad.clone(),
                         Arc::clone(&config.time_provider),
+                        config.parquet_writer_options.clone(),
                     ),
                     Arc::clone(&config.exec),
                 ),
@@ -307,6 +336,9 @@ pub fn hardcoded_components(config: &Config) -> Arc<Components> {
         df_planner: Arc::new(V1DataFusionPlanner::new(
             config.parquet_store_scratchpad.clone(),
             Arc::clone(&config.exec),
+            config.enable_round_robin_repartition,
+            config.target_partitions,
+            config.min_file_bytes_to_split,
         )),
         df_plan_exec,
         parquet_files_sink,
@@ -336,6 +368,16 @@ fn version_specific_partition_filters(config: &Config) -> Vec<Arc<dyn PartitionF
                 LevelRangeFileFilter::new(CompactionLevel::Initial..=CompactionLevel::Initial),
             ))]
         }
+        // Forced full compaction: admit any partition that still has files below the
+        // bottommost target level (L2), ignoring the size/count thresholds below. Used
+        // for operator-triggered backfill compaction.
+        AlgoVersion::TargetLevel if config.force_bottommost => {
+            vec![Arc::new(HasMatchingFilePartitionFilter::new(
+                LevelRangeFileFilter::new(
+                    CompactionLevel::Initial..=CompactionLevel::FileNonOverlapped,
+                ),
+            ))]
+        }
         // (Has-L0) OR            -- to avoid overlaped files
         // (num(L1) > N) OR       -- to avoid many files
         // (total_size(L1) > max_desired_file_size)  -- to avoid compact and than split
@@ -362,13 +404,24 @@ fn version_specific_partition_filters(config: &Config) -> Vec<Arc<dyn PartitionF
 }
 
 fn version_specific_file_classifier(config: &Config) -> Arc<dyn FileClassifier> {
-    match config.compact_version {
-        AlgoVersion::AllAtOnce => Arc::new(AllAtOnceFileClassifier::new()),
-        AlgoVersion::TargetLevel => Arc::new(SplitBasedFileClassifier::new(
+    match config.components.file_classifier {
+        FileClassifierKind::AllAtOnce => Arc::new(AllAtOnceFileClassifier::new()),
+        // Forced full compaction always drives the partition down to L2.
+        FileClassifierKind::SplitBased if config.force_bottommost => {
+            Arc::new(SplitBasedFileClassifier::new(
+                BottommostTargetLevelChooser::new(),
+                TargetLevelTargetLevelSplit::new(),
+                TargetLevelNonOverlapSplit::new(),
+                TargetLevelUpgradeSplit::new(config.max_desired_file_size_bytes),
+                TrivialMoveSplit::new(config.max_desired_file_size_bytes),
+            ))
+        }
+        FileClassifierKind::SplitBased => Arc::new(SplitBasedFileClassifier::new(
             TargetLevelTargetLevelChooser::new(OneLevelExist::new()),
             TargetLevelTargetLevelSplit::new(),
             TargetLevelNonOverlapSplit::new(),
             TargetLevelUpgradeSplit::new(config.max_desired_file_size_bytes),
+            TrivialMoveSplit::new(config.max_desired_file_size_bytes),
         )),
     }
 }
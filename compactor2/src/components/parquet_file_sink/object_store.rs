@@ -0,0 +1,109 @@
+use std::{fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use data_types::{CompactionLevel, ParquetFileParams, ShardId};
+use datafusion::{error::DataFusionError, physical_plan::SendableRecordBatchStream};
+use iox_time::{Time, TimeProvider};
+use parquet_file::{
+    metadata::IoxMetadata,
+    serialize::CodecError,
+    storage::{ParquetStorage, UploadError},
+};
+use uuid::Uuid;
+
+use crate::{
+    config::ParquetWriterOptions, partition_info::PartitionInfo,
+};
+
+use super::ParquetFileSink;
+
+/// Sink that writes compacted parquet files directly to an object store.
+#[derive(Debug)]
+pub struct ObjectStoreParquetFileSink {
+    shard_id: ShardId,
+    store: ParquetStorage,
+    time_provider: Arc<dyn TimeProvider>,
+    writer_options: ParquetWriterOptions,
+}
+
+impl ObjectStoreParquetFileSink {
+    /// Create a new sink, writing via `store` with the given encoder `writer_options`.
+    pub fn new(
+        shard_id: ShardId,
+        store: ParquetStorage,
+        time_provider: Arc<dyn TimeProvider>,
+        writer_options: ParquetWriterOptions,
+    ) -> Self {
+        Self {
+            shard_id,
+            store,
+            time_provider,
+            writer_options,
+        }
+    }
+}
+
+impl Display for ObjectStoreParquetFileSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "object_store")
+    }
+}
+
+#[async_trait]
+impl ParquetFileSink for ObjectStoreParquetFileSink {
+    async fn store(
+        &self,
+        stream: SendableRecordBatchStream,
+        partition: Arc<PartitionInfo>,
+        level: CompactionLevel,
+        max_l0_created_at: Time,
+    ) -> Result<Option<ParquetFileParams>, DataFusionError> {
+        let meta = IoxMetadata {
+            object_store_id: Uuid::new_v4(),
+            creation_timestamp: self.time_provider.now(),
+            shard_id: self.shard_id,
+            namespace_id: partition.namespace_id,
+            namespace_name: partition.namespace_name.clone().into(),
+            table_id: partition.table.id,
+            table_name: partition.table.name.clone().into(),
+            partition_id: partition.partition_id,
+            partition_key: partition.partition_key.clone(),
+            max_sequence_number: data_types::SequenceNumber::new(0),
+            compaction_level: level,
+            sort_key: partition.sort_key.clone(),
+            max_l0_created_at,
+        };
+
+        // Translate the operator-configured encoder settings into concrete writer properties
+        // for this upload. Errors here are configuration errors (e.g. an out-of-range codec
+        // level) and are surfaced as execution errors for the partition.
+        let writer_properties =
+            self.writer_options
+                .to_writer_properties()
+                .map_err(|e| DataFusionError::External(Box::new(e)))?;
+
+        // Stream the record batches to the object store.
+        let (parquet_meta, file_size) = match self
+            .store
+            .upload(stream, &meta, writer_properties)
+            .await
+        {
+            Ok(v) => v,
+            Err(UploadError::Serialise(CodecError::NoRows)) => {
+                // No data
+                return Ok(None);
+            }
+            Err(e) => {
+                return Err(DataFusionError::External(Box::new(e)));
+            }
+        };
+
+        let parquet_file = meta.to_parquet_file(partition.partition_id, file_size, &parquet_meta, |name| {
+            partition
+                .column_id(name)
+                .expect("unknown column")
+        });
+
+        Ok(Some(parquet_file))
+    }
+}
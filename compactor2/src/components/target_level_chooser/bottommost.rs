@@ -0,0 +1,31 @@
+use std::fmt::Display;
+
+use data_types::{CompactionLevel, ParquetFile};
+
+use super::TargetLevelChooser;
+
+/// Always targets the bottommost level ([`CompactionLevel::Final`], i.e. L2).
+///
+/// Used by the forced full-compaction mode so a partition is driven all the way down in one
+/// pass regardless of how few or small its current files are.
+#[derive(Debug, Default)]
+pub struct BottommostTargetLevelChooser;
+
+impl BottommostTargetLevelChooser {
+    /// Create a new [`BottommostTargetLevelChooser`].
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Display for BottommostTargetLevelChooser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "bottommost")
+    }
+}
+
+impl TargetLevelChooser for BottommostTargetLevelChooser {
+    fn detect(&self, _files: &[ParquetFile]) -> CompactionLevel {
+        CompactionLevel::Final
+    }
+}
@@ -0,0 +1,14 @@
+//! Pick the target [`CompactionLevel`] a partition's files should be compacted into.
+
+use std::fmt::{Debug, Display};
+
+use data_types::{CompactionLevel, ParquetFile};
+
+pub mod bottommost;
+pub mod target_level;
+
+/// Choose the target level for a compaction round.
+pub trait TargetLevelChooser: Debug + Display + Send + Sync {
+    /// Determine the destination [`CompactionLevel`] for `files`.
+    fn detect(&self, files: &[ParquetFile]) -> CompactionLevel;
+}
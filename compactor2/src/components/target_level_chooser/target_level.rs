@@ -0,0 +1,52 @@
+use std::fmt::Display;
+
+use data_types::{CompactionLevel, ParquetFile};
+
+use crate::components::level_exist::LevelExist;
+
+use super::TargetLevelChooser;
+
+/// Targets the next level up from the lowest level still present in the partition.
+///
+/// While L0 files remain the partition is compacted towards L1
+/// ([`CompactionLevel::FileNonOverlapped`]); once only L1 files remain it is compacted towards
+/// L2 ([`CompactionLevel::Final`]).
+#[derive(Debug)]
+pub struct TargetLevelTargetLevelChooser<T>
+where
+    T: LevelExist,
+{
+    level_exist: T,
+}
+
+impl<T> TargetLevelTargetLevelChooser<T>
+where
+    T: LevelExist,
+{
+    /// Create a new [`TargetLevelTargetLevelChooser`].
+    pub fn new(level_exist: T) -> Self {
+        Self { level_exist }
+    }
+}
+
+impl<T> Display for TargetLevelTargetLevelChooser<T>
+where
+    T: LevelExist,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "target_level")
+    }
+}
+
+impl<T> TargetLevelChooser for TargetLevelTargetLevelChooser<T>
+where
+    T: LevelExist,
+{
+    fn detect(&self, files: &[ParquetFile]) -> CompactionLevel {
+        if self.level_exist.apply(files, CompactionLevel::Initial) {
+            CompactionLevel::FileNonOverlapped
+        } else {
+            CompactionLevel::Final
+        }
+    }
+}
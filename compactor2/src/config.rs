@@ -0,0 +1,591 @@
+//! Config-related stuff.
+
+use std::{collections::HashSet, num::NonZeroUsize, sync::Arc, time::Duration};
+
+use backoff::BackoffConfig;
+use data_types::{PartitionId, ShardId};
+use iox_catalog::interface::Catalog;
+use iox_query::exec::Executor;
+use iox_time::TimeProvider;
+use parquet::{
+    basic::{BrotliLevel, Compression, GzipLevel, ZstdLevel},
+    file::properties::{WriterProperties, WriterVersion},
+};
+use parquet_file::storage::ParquetStorage;
+
+use crate::components::parquet_files_sink::ParquetFilesSink;
+
+/// Config to set up a compactor.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Shard Id
+    pub shard_id: ShardId,
+
+    /// Metric registry.
+    pub metric_registry: Arc<metric::Registry>,
+
+    /// Central catalog.
+    pub catalog: Arc<dyn Catalog>,
+
+    /// Store holding the actual parquet files.
+    pub parquet_store_real: ParquetStorage,
+
+    /// Store holding temporary files.
+    pub parquet_store_scratchpad: ParquetStorage,
+
+    /// Executor.
+    pub exec: Arc<Executor>,
+
+    /// Time provider.
+    pub time_provider: Arc<dyn TimeProvider>,
+
+    /// Backoff config.
+    pub backoff_config: BackoffConfig,
+
+    /// Number of partitions that should be compacted in parallel.
+    pub partition_concurrency: NonZeroUsize,
+
+    /// Number of concurrent parquet store operations per scratchpad.
+    pub partition_scratchpad_concurrency: NonZeroUsize,
+
+    /// Desired max size of compacted parquet files.
+    /// It is a target desired value, rather than a guarantee.
+    pub max_desired_file_size_bytes: u64,
+
+    /// Percentage of desired max file size.
+    /// If the estimated compacted result is too small, no need to split it.
+    /// This percentage is to determine how small it is:
+    ///    < percentage_max_file_size * max_desired_file_size_bytes:
+    /// This value must be between (0, 100)
+    pub percentage_max_file_size: u16,
+
+    /// Split file percentage
+    /// If the estimated compacted result is neither too small nor too large, it will be split
+    /// into 2 files determined by this percentage.
+    ///    . Too large means: > max_desired_file_size_bytes
+    ///    . Too small means: < percentage_max_file_size * max_desired_file_size_bytes
+    ///    . Any size in the middle will be considered neither too small nor too large
+    /// This value must be between (0, 100)
+    pub split_percentage: u16,
+
+    /// Maximum duration of the per-partition compaction task.
+    pub partition_timeout: Duration,
+
+    /// Shadow mode.
+    ///
+    /// This will NOT write / commit any output to the object store or catalog.
+    ///
+    /// This is mostly useful for debugging.
+    pub shadow_mode: bool,
+
+    /// Ignores "partition marked w/ error and shall be skipped" entries in the catalog.
+    ///
+    /// This is mostly useful for debugging.
+    pub ignore_partition_skip_marker: bool,
+
+    /// Maximum number of files per compaction plan.
+    pub max_input_files_per_partition: usize,
+
+    /// Maximum input bytes (in parquet) per compaction plan. If there is more data, we ignore the partition (for now)
+    /// as a self-protection mechanism.
+    pub max_input_parquet_bytes_per_partition: usize,
+
+    /// Shard config (if sharding should be enabled).
+    pub shard_config: Option<ShardConfig>,
+
+    /// Compact version.
+    pub compact_version: AlgoVersion,
+
+    /// Minimum number of L1 files to compact to L2.
+    pub min_num_l1_files_to_compact: usize,
+
+    /// Only process all discovered partitions once.
+    pub process_once: bool,
+
+    /// Marks a compactor run as simulated w/o any object store interaction.
+    ///
+    /// Component assembly does not read this flag: whether parquet files are read or written is
+    /// selected by [`ComponentsConfig::scratchpad`], [`ComponentsConfig::df_plan_exec`] and
+    /// [`parquet_files_sink_override`](Self::parquet_files_sink_override).
+    pub simulate_without_object_store: bool,
+
+    /// Use the provided [`ParquetFilesSink`] to write parquet files.
+    pub parquet_files_sink_override: Option<Arc<dyn ParquetFilesSink>>,
+
+    /// Immediately stop the compactor if an error occurs, rather than skipping the affected
+    /// partition and moving on.
+    pub all_errors_are_fatal: bool,
+
+    /// Maximum number of columns a table may have to be considered for compaction.
+    pub max_num_columns_per_table: usize,
+
+    /// Threshold for "recently written" partitions.
+    pub partition_threshold: Duration,
+
+    /// Writer-side encoder settings used when the compaction output sink writes parquet files.
+    pub parquet_writer_options: ParquetWriterOptions,
+
+    /// Force a full compaction of the selected partitions down to the bottommost target level
+    /// (L2) in a single pass, bypassing the usual size/count thresholds.
+    ///
+    /// This is intended for operator-triggered backfill compaction and only takes effect for
+    /// [`AlgoVersion::TargetLevel`].
+    pub force_bottommost: bool,
+
+    /// Declarative selection of which implementation of each component trait is assembled.
+    pub components: ComponentsConfig,
+
+    /// Insert round-robin `RepartitionExec` nodes above the parquet scans in the compaction
+    /// plan that have no sort, sort-preserving merge, dedup or split above them. Scans beneath
+    /// such an operator are not repartitioned, so the work of those operators does not fan out.
+    pub enable_round_robin_repartition: bool,
+
+    /// Number of partitions the round-robin repartitioning fans out to.
+    ///
+    /// Has no effect unless [`enable_round_robin_repartition`](Self::enable_round_robin_repartition)
+    /// is set.
+    pub target_partitions: usize,
+
+    /// Minimum input parquet file size, in bytes, at which the file is split into multiple
+    /// row-group-aligned scan ranges so it can be read concurrently within one compaction.
+    ///
+    /// `0` disables byte-range splitting and treats every file as a single scan unit.
+    pub min_file_bytes_to_split: u64,
+}
+
+/// Declarative selection of the compactor's pluggable components.
+///
+/// Each field selects which implementation of a component trait
+/// [`hardcoded_components`](crate::components::hardcoded::hardcoded_components) instantiates, so
+/// the compactor can be reconfigured from CLI/env/TOML without rebuilding. Nesting mirrors
+/// DataFusion's `config_namespace!` style: the struct is a plain value that is populated during
+/// config parsing and [validated](ComponentsConfig::validate) at startup.
+#[derive(Debug, Clone)]
+pub struct ComponentsConfig {
+    /// Source of partitions to consider for compaction.
+    pub partitions_source: PartitionsSourceConfig,
+
+    /// Set of partition filters applied (in wrapper order) to decide whether a partition is
+    /// compacted.
+    pub partition_filters: PartitionFilterSet,
+
+    /// Sink that records successfully compacted partitions.
+    pub commit: CommitSink,
+
+    /// Sink that records that a partition is done (or failed).
+    pub partition_done_sink: PartitionDoneSinkKind,
+
+    /// File classifier that turns a partition's files into a compaction plan.
+    pub file_classifier: FileClassifierKind,
+
+    /// Scratchpad used to stage files during compaction.
+    pub scratchpad: ScratchpadKind,
+
+    /// Executor that runs the DataFusion compaction plan.
+    pub df_plan_exec: DataFusionPlanExecKind,
+}
+
+impl ComponentsConfig {
+    /// Validate the selected components against `shadow_mode`.
+    ///
+    /// This is called once at startup so operator misconfiguration surfaces as an error before
+    /// any component is assembled, rather than as a panic during assembly.
+    pub fn validate(&self, shadow_mode: bool) -> Result<(), ComponentsConfigError> {
+        if shadow_mode {
+            // Shadow mode is a read-only dry run, so any sink that mutates the catalog or object
+            // store must not be selected.
+            if self.commit == CommitSink::Catalog {
+                return Err(ComponentsConfigError::ShadowModeMutatingSink {
+                    component: "commit",
+                });
+            }
+            if self.partition_done_sink == PartitionDoneSinkKind::Catalog {
+                return Err(ComponentsConfigError::ShadowModeMutatingSink {
+                    component: "partition_done_sink",
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error validating a [`ComponentsConfig`].
+#[derive(Debug, thiserror::Error)]
+pub enum ComponentsConfigError {
+    /// A catalog-mutating sink was selected together with shadow mode.
+    #[error("{component} sink writes to the catalog and cannot be used with shadow_mode")]
+    ShadowModeMutatingSink {
+        /// Name of the offending component.
+        component: &'static str,
+    },
+
+    /// The selected file classifier does not match the compaction algorithm version.
+    #[error(
+        "compact_version {compact_version:?} and components.file_classifier \
+         {file_classifier:?} are incoherent"
+    )]
+    IncoherentClassifier {
+        /// The configured algorithm version.
+        compact_version: AlgoVersion,
+        /// The configured file classifier.
+        file_classifier: FileClassifierKind,
+    },
+}
+
+/// Selects which [`PartitionFilter`](crate::components::partition_filter::PartitionFilter) stack
+/// is assembled.
+///
+/// This is the knob used to A/B test new filters in production: `Standard` is the full
+/// production stack, `Unfiltered` drops the skip-marker, column and size/count gates and only
+/// requires a partition to have files. The separate resource-limit filter (max input files and
+/// max input parquet bytes per partition) applies under both sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionFilterSet {
+    /// Standard production filter stack (skip marker, column and size/count thresholds).
+    Standard,
+    /// Require only that the partition has files; apply no other gate.
+    Unfiltered,
+}
+
+/// Selects the [`Commit`](crate::components::commit::Commit) implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitSink {
+    /// Commit compaction results to the catalog.
+    Catalog,
+    /// Discard commits (used by shadow mode / tests).
+    Mock,
+}
+
+/// Selects the [`PartitionDoneSink`](crate::components::partition_done_sink::PartitionDoneSink)
+/// implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionDoneSinkKind {
+    /// Record partition completion in the catalog.
+    Catalog,
+    /// Discard completion markers (used by shadow mode / tests).
+    Mock,
+}
+
+/// Selects the [`FileClassifier`](crate::components::file_classifier::FileClassifier)
+/// implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileClassifierKind {
+    /// Compact all files of a partition in one plan.
+    AllAtOnce,
+    /// Split-based, level-by-level classification.
+    SplitBased,
+}
+
+/// Selects the [`ScratchpadGen`](crate::components::scratchpad::ScratchpadGen) implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScratchpadKind {
+    /// Stage files through the object store.
+    Prod,
+    /// Do not stage files (used when simulating without an object store).
+    Noop,
+}
+
+/// Selects the
+/// [`DataFusionPlanExec`](crate::components::df_plan_exec::DataFusionPlanExec) implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFusionPlanExecKind {
+    /// Run the plan on a dedicated executor.
+    Dedicated,
+    /// Do not run the plan (used when simulating without an object store).
+    Noop,
+}
+
+impl Config {
+    /// Validate the configuration at startup, before any component is assembled.
+    ///
+    /// This rejects internally inconsistent selections (e.g. a catalog-mutating sink together
+    /// with shadow mode, or a file classifier that disagrees with `compact_version`) so the
+    /// misconfiguration surfaces as an error rather than as a panic or silently-wrong behavior
+    /// later on.
+    pub fn validate(&self) -> Result<(), ComponentsConfigError> {
+        self.components.validate(self.shadow_mode)?;
+
+        // The partition/files filters are still selected by `compact_version`, so it must agree
+        // with the classifier selected in the components namespace.
+        if !classifier_coherent(self.compact_version, self.components.file_classifier) {
+            return Err(ComponentsConfigError::IncoherentClassifier {
+                compact_version: self.compact_version,
+                file_classifier: self.components.file_classifier,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether `compact_version` and `file_classifier` select a coherent compaction stack.
+pub(crate) fn classifier_coherent(
+    compact_version: AlgoVersion,
+    file_classifier: FileClassifierKind,
+) -> bool {
+    matches!(
+        (compact_version, file_classifier),
+        (AlgoVersion::AllAtOnce, FileClassifierKind::AllAtOnce)
+            | (AlgoVersion::TargetLevel, FileClassifierKind::SplitBased)
+    )
+}
+
+/// Shard config.
+#[derive(Debug, Clone)]
+pub struct ShardConfig {
+    /// Number of shards.
+    pub n_shards: usize,
+
+    /// Shard ID.
+    ///
+    /// Starts as 0 and must be smaller than the number of shards.
+    pub shard_id: usize,
+}
+
+/// Partitions source config.
+#[derive(Debug, Clone)]
+pub enum PartitionsSourceConfig {
+    /// Use the catalog to determine which partitions have recently received writes.
+    CatalogRecentWrites,
+
+    /// Use all partitions from the catalog.
+    ///
+    /// This does NOT consider if/when a partition received any writes.
+    CatalogAll,
+
+    /// Use a fixed set of partitions.
+    ///
+    /// This is mostly useful for debugging.
+    Fixed(HashSet<PartitionId>),
+}
+
+/// Compaction algorithm version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlgoVersion {
+    /// Compact all files of a partition in a single DataFusion plan.
+    AllAtOnce,
+
+    /// Compact files level-by-level towards the target level.
+    TargetLevel,
+}
+
+/// On-disk encoder settings for the parquet files produced by compaction.
+///
+/// This mirrors the per-file tuning surface exposed for parquet writers elsewhere in the
+/// ecosystem and lets operators trade compaction CPU for on-disk size (e.g. `zstd` for cold
+/// partitions) without recompiling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParquetWriterOptions {
+    /// Compression codec applied to data pages.
+    pub compression: ParquetCompression,
+
+    /// Whether dictionary encoding is enabled.
+    pub dictionary_enabled: bool,
+
+    /// Soft limit on the uncompressed size of a data page, in bytes.
+    pub data_page_size_limit: usize,
+
+    /// Number of rows buffered before an encoding batch is flushed.
+    pub write_batch_size: usize,
+
+    /// Parquet format version to emit.
+    pub writer_version: ParquetWriterVersion,
+}
+
+impl Default for ParquetWriterOptions {
+    fn default() -> Self {
+        // These match the historical hardcoded encoder settings so existing deployments keep
+        // writing byte-identical files unless an operator opts into different tuning.
+        Self {
+            compression: ParquetCompression::Zstd(1),
+            dictionary_enabled: true,
+            data_page_size_limit: 1024 * 1024,
+            write_batch_size: 1024,
+            writer_version: ParquetWriterVersion::V1_0,
+        }
+    }
+}
+
+impl ParquetWriterOptions {
+    /// Translate these options into the [`WriterProperties`] consumed by the parquet writer.
+    ///
+    /// [`WriterProperties`]: parquet::file::properties::WriterProperties
+    pub fn to_writer_properties(&self) -> Result<WriterProperties, ParquetWriterOptionsError> {
+        let builder = WriterProperties::builder()
+            .set_compression(self.compression.try_into()?)
+            .set_dictionary_enabled(self.dictionary_enabled)
+            .set_data_page_size_limit(self.data_page_size_limit)
+            .set_write_batch_size(self.write_batch_size)
+            .set_writer_version(self.writer_version.into());
+
+        Ok(builder.build())
+    }
+}
+
+impl TryFrom<ParquetCompression> for Compression {
+    type Error = ParquetWriterOptionsError;
+
+    fn try_from(compression: ParquetCompression) -> Result<Self, Self::Error> {
+        Ok(match compression {
+            ParquetCompression::Uncompressed => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Lz4 => Compression::LZ4,
+            ParquetCompression::Gzip(level) => Compression::GZIP(
+                GzipLevel::try_new(level).map_err(|e| ParquetWriterOptionsError::Level {
+                    codec: "gzip",
+                    source: e.to_string(),
+                })?,
+            ),
+            ParquetCompression::Brotli(level) => Compression::BROTLI(
+                BrotliLevel::try_new(level).map_err(|e| ParquetWriterOptionsError::Level {
+                    codec: "brotli",
+                    source: e.to_string(),
+                })?,
+            ),
+            ParquetCompression::Zstd(level) => Compression::ZSTD(
+                ZstdLevel::try_new(level).map_err(|e| ParquetWriterOptionsError::Level {
+                    codec: "zstd",
+                    source: e.to_string(),
+                })?,
+            ),
+        })
+    }
+}
+
+impl From<ParquetWriterVersion> for WriterVersion {
+    fn from(version: ParquetWriterVersion) -> Self {
+        match version {
+            ParquetWriterVersion::V1_0 => WriterVersion::PARQUET_1_0,
+            ParquetWriterVersion::V2_0 => WriterVersion::PARQUET_2_0,
+        }
+    }
+}
+
+/// Error translating [`ParquetWriterOptions`] into writer properties.
+#[derive(Debug, thiserror::Error)]
+pub enum ParquetWriterOptionsError {
+    /// A codec was configured with a level it does not accept.
+    #[error("invalid {codec} compression level: {source}")]
+    Level {
+        /// Codec the invalid level was configured for.
+        codec: &'static str,
+        /// Underlying error from the codec.
+        source: String,
+    },
+}
+
+/// Compression codec for compacted parquet files.
+///
+/// The level-carrying variants accept the raw level understood by the underlying codec; invalid
+/// levels are rejected when the options are translated into [`WriterProperties`].
+///
+/// [`WriterProperties`]: parquet::file::properties::WriterProperties
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParquetCompression {
+    /// No compression.
+    Uncompressed,
+    /// Snappy.
+    Snappy,
+    /// Gzip at the given level.
+    Gzip(u32),
+    /// Zstandard at the given level.
+    Zstd(i32),
+    /// LZ4.
+    Lz4,
+    /// Brotli at the given quality level.
+    Brotli(u32),
+}
+
+/// Parquet format version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParquetWriterVersion {
+    /// Parquet format `1.0`.
+    V1_0,
+    /// Parquet format `2.0`.
+    V2_0,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn components(
+        commit: CommitSink,
+        partition_done_sink: PartitionDoneSinkKind,
+        file_classifier: FileClassifierKind,
+    ) -> ComponentsConfig {
+        ComponentsConfig {
+            partitions_source: PartitionsSourceConfig::Fixed(HashSet::new()),
+            partition_filters: PartitionFilterSet::Standard,
+            commit,
+            partition_done_sink,
+            file_classifier,
+            scratchpad: ScratchpadKind::Prod,
+            df_plan_exec: DataFusionPlanExecKind::Dedicated,
+        }
+    }
+
+    #[test]
+    fn shadow_mode_allows_mock_sinks() {
+        let cfg = components(
+            CommitSink::Mock,
+            PartitionDoneSinkKind::Mock,
+            FileClassifierKind::SplitBased,
+        );
+        assert!(cfg.validate(true).is_ok());
+    }
+
+    #[test]
+    fn shadow_mode_rejects_catalog_commit() {
+        let cfg = components(
+            CommitSink::Catalog,
+            PartitionDoneSinkKind::Mock,
+            FileClassifierKind::SplitBased,
+        );
+        assert!(matches!(
+            cfg.validate(true),
+            Err(ComponentsConfigError::ShadowModeMutatingSink {
+                component: "commit"
+            })
+        ));
+        // Without shadow mode the same catalog sink is fine.
+        assert!(cfg.validate(false).is_ok());
+    }
+
+    #[test]
+    fn shadow_mode_rejects_catalog_partition_done_sink() {
+        let cfg = components(
+            CommitSink::Mock,
+            PartitionDoneSinkKind::Catalog,
+            FileClassifierKind::SplitBased,
+        );
+        assert!(matches!(
+            cfg.validate(true),
+            Err(ComponentsConfigError::ShadowModeMutatingSink {
+                component: "partition_done_sink"
+            })
+        ));
+    }
+
+    #[test]
+    fn classifier_coherence() {
+        assert!(classifier_coherent(
+            AlgoVersion::AllAtOnce,
+            FileClassifierKind::AllAtOnce
+        ));
+        assert!(classifier_coherent(
+            AlgoVersion::TargetLevel,
+            FileClassifierKind::SplitBased
+        ));
+        assert!(!classifier_coherent(
+            AlgoVersion::AllAtOnce,
+            FileClassifierKind::SplitBased
+        ));
+        assert!(!classifier_coherent(
+            AlgoVersion::TargetLevel,
+            FileClassifierKind::AllAtOnce
+        ));
+    }
+}
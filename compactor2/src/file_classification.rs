@@ -0,0 +1,22 @@
+//! Result of classifying a partition's files for a compaction round.
+
+use data_types::{CompactionLevel, ParquetFile};
+
+/// How a partition's files should be handled in one compaction round.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileClassification {
+    /// Target level the round compacts towards.
+    pub target_level: CompactionLevel,
+
+    /// Files that must be rewritten through a DataFusion plan and written back to the object
+    /// store.
+    pub files_to_compact: Vec<ParquetFile>,
+
+    /// Files that only need their `compaction_level` bumped to `target_level` via a catalog-only
+    /// commit. These are never fed to a DataFusion plan and their bytes are never read or
+    /// rewritten.
+    pub files_to_upgrade: Vec<ParquetFile>,
+
+    /// Files that take no part in this round.
+    pub files_to_keep: Vec<ParquetFile>,
+}